@@ -2,24 +2,70 @@ use std::string::String;
 #[allow(unused_imports)]
 use std::str::Utf8Error;
 
+/// A backing store a [StringBuilder] can append into.
+///
+/// Implemented for [String] (the default, used by [StringBuilder::new] and friends)
+/// and, behind the `bumpalo` feature, for `bumpalo::collections::String` so a builder
+/// can append into a bump arena instead of the global allocator.
+pub trait Buffer: Sized {
+    fn buf_push_str(&mut self, s: &str);
+    fn buf_push(&mut self, c: char);
+    fn buf_reserve(&mut self, additional: usize);
+}
+
+impl Buffer for String {
+    fn buf_push_str(&mut self, s: &str) {
+        self.push_str(s);
+    }
+    fn buf_push(&mut self, c: char) {
+        self.push(c);
+    }
+    fn buf_reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+}
+
+#[cfg(feature = "bumpalo")]
+impl<'bump> Buffer for bumpalo::collections::String<'bump> {
+    fn buf_push_str(&mut self, s: &str) {
+        self.push_str(s);
+    }
+    fn buf_push(&mut self, c: char) {
+        self.push(c);
+    }
+    fn buf_reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+}
+
 /// StringBuilder type for ergonometric construction of a [String].
 ///
 /// Various kinds of string and byte sequences can be appended to it, using the "builder" pattern.
-/// 
+///
 /// # Example
 /// ```rust
 /// use string_builder::StringBuilder;
 ///
 /// let s2 = "ghi".to_string();
 ///
-/// let mut s = StringBuilder::new()    // receiver must be declared `mut`  
+/// let mut s = StringBuilder::new()    // receiver must be declared `mut`
 ///         .append("abc")
 ///         .append("def")              // takes &str naturally
 ///         .append(&s2)                // needs `&` for strings
-///         .append(&format!("jkl"))    // ... and for format! results
 ///         .to_string();               // access constructed string via .to_string()
 ///
-/// assert_eq!(s, "abcdefghijkl");
+/// assert_eq!(s, "abcdefghi");
+/// ```
+///
+/// [StringBuilder] also implements [std::fmt::Write], so `write!` can target it
+/// directly instead of formatting into an intermediate [String]:
+/// ```rust
+/// use std::fmt::Write;
+/// use string_builder::StringBuilder;
+///
+/// let mut b = StringBuilder::new();
+/// write!(&mut b, "x={} y={}", 1, 2).unwrap();
+/// assert_eq!(b.to_string(), "x=1 y=2");
 /// ```
 ///
 /// Various ways to construct the builder:
@@ -31,47 +77,141 @@ use std::str::Utf8Error;
 ///                                                     // may avoid redundant reallocations
 /// ```
 ///
+/// `StringBuilder` is generic over its [Buffer]; the default, `String`, is used unless
+/// the `bumpalo` feature is enabled and `StringBuilder::new_in` is used to build into
+/// a bump arena instead.
+///
 /// Use builder pattern:
 ///
-pub struct StringBuilder(String);
+pub struct StringBuilder<B: Buffer = String> {
+    buf: B,
+    /// Incomplete utf8 sequence left over from the last [Self::append_chunk], if any.
+    tail: [u8; MAX_UTF8_CHAR_LEN],
+    tail_len: u8,
+}
 
-impl StringBuilder {
+/// Max length in bytes of a single utf8-encoded codepoint, and so the size of the
+/// buffer for the (at most one fewer) trailing bytes [StringBuilder::append_chunk]
+/// carries forward.
+const MAX_UTF8_CHAR_LEN: usize = 4;
+
+impl StringBuilder<String> {
     /// Construct with empty string of default capacity
     pub fn new() -> Self {
-        Self(String::new())
+        Self {
+            buf: String::new(),
+            tail: [0; MAX_UTF8_CHAR_LEN],
+            tail_len: 0,
+        }
     }
     /// Construct with empty string, but your estimated capacity
     ///
     /// A good guess can reduce number of intermediate buffer allocations and data moves.
     pub fn with_capacity(size: usize) -> Self {
-        Self(String::with_capacity(size))
+        Self {
+            buf: String::with_capacity(size),
+            tail: [0; MAX_UTF8_CHAR_LEN],
+            tail_len: 0,
+        }
     }
     /// Construct with non-empty initial value
     pub fn from(from: &str) -> Self {
-        Self(from.to_string())
+        Self {
+            buf: from.to_string(),
+            tail: [0; MAX_UTF8_CHAR_LEN],
+            tail_len: 0,
+        }
     }
 
+    /// Extract newly-built [String] at end of chain.
+    ///
+    /// # Panics
+    /// Panics if [Self::append_chunk] left an incomplete utf8 sequence buffered,
+    /// i.e. the stream ended mid-codepoint.
+    pub fn to_string(self) -> String {
+        assert_eq!(
+            self.tail_len, 0,
+            "StringBuilder::to_string: stream ended mid-codepoint"
+        );
+        self.buf
+    }
+}
+
+#[cfg(feature = "bumpalo")]
+impl<'bump> StringBuilder<bumpalo::collections::String<'bump>> {
+    /// Construct a builder that appends into a bump-allocated arena instead of the
+    /// global allocator, so code assembling many short-lived strings in a hot loop
+    /// can free everything at once when `bump` drops. Requires the `bumpalo` feature.
+    pub fn new_in(bump: &'bump bumpalo::Bump) -> Self {
+        Self {
+            buf: bumpalo::collections::String::new_in(bump),
+            tail: [0; MAX_UTF8_CHAR_LEN],
+            tail_len: 0,
+        }
+    }
+
+    /// Extract the newly-built, arena-backed [str] at the end of the chain.
+    ///
+    /// # Panics
+    /// Panics if [Self::append_chunk] left an incomplete utf8 sequence buffered,
+    /// i.e. the stream ended mid-codepoint.
+    pub fn into_bump_str(self) -> &'bump str {
+        assert_eq!(
+            self.tail_len, 0,
+            "StringBuilder::into_bump_str: stream ended mid-codepoint"
+        );
+        self.buf.into_bump_str()
+    }
+}
+
+impl<B: Buffer> StringBuilder<B> {
     /// Append a [std::str] or `&`[sdt::string::String]
     pub fn append(mut self, from: &str) -> Self {
-        self.0.push_str(from);
+        self.buf.buf_push_str(from);
+        self
+    }
+
+    /// Append a single [char].
+    pub fn append_char(mut self, c: char) -> Self {
+        self.buf.buf_push(c);
+        self
+    }
+
+    /// Append `from` to the builder `n` times, reserving space for all of it up front.
+    ///
+    /// Handy for padding or indentation, e.g. `append_repeated("  ", depth)`.
+    pub fn append_repeated(mut self, from: &str, n: usize) -> Self {
+        self.buf.buf_reserve(from.len() * n);
+        for _ in 0..n {
+            self.buf.buf_push_str(from);
+        }
+        self
+    }
+
+    /// Append a single [char] to the builder `n` times, reserving space for all of it up front.
+    pub fn append_char_repeated(mut self, c: char, n: usize) -> Self {
+        self.buf.buf_reserve(c.len_utf8() * n);
+        for _ in 0..n {
+            self.buf.buf_push(c);
+        }
         self
     }
 
     /// Append an array of bytes.  Panics if [from] is not well-formed utf8.
     pub fn append_bytes(mut self, from: &[u8]) -> Self {
         let from_bytes = std::str::from_utf8(from).unwrap();
-        self.0.push_str(from_bytes);
+        self.buf.buf_push_str(from_bytes);
         self
     }
 
     /// Fallible method for appending bytes.
-    /// 
-    /// If panic's not your style, you can handle the potential [Utf8Error] 
-    /// 
+    ///
+    /// If panic's not your style, you can handle the potential [Utf8Error]
+    ///
     /// ```rust
     /// use string_builder::StringBuilder;
     /// use std::error::Error;
-    /// 
+    ///
     /// fn my_fn() -> Result<(), Box<dyn Error>> {
     ///     let some_bytes = "Pelé".as_bytes(); // last char is actually 2 bytes 0xc3_a9
     ///     let s = StringBuilder::new()
@@ -84,14 +224,182 @@ impl StringBuilder {
     /// ```
     pub fn try_append_bytes(mut self, from: &[u8]) -> Result<Self, std::str::Utf8Error> {
         let from_bytes = std::str::from_utf8(from)?;
-        self.0.push_str(from_bytes);
+        self.buf.buf_push_str(from_bytes);
 
         Ok(self)
     }
 
-    /// Extract newly-built [String] at end of chain.
-    pub fn to_string(self) -> String {
-        self.0
+    /// Append a chunk of bytes from a stream that isn't guaranteed to split on
+    /// codepoint boundaries, e.g. successive reads off a socket or file.
+    ///
+    /// Unlike [Self::append_bytes] / [Self::try_append_bytes], a chunk that ends
+    /// mid-codepoint is not an error: the incomplete trailing bytes (at most
+    /// [MAX_UTF8_CHAR_LEN] - 1 of them) are carried forward and completed by the
+    /// start of the next chunk. A chunk containing a genuinely malformed sequence
+    /// still returns the [Utf8Error]. Call [Self::to_string] only after the last
+    /// chunk; it panics if a trailing incomplete sequence was never completed.
+    ///
+    /// ```rust
+    /// use string_builder::StringBuilder;
+    ///
+    /// let some_bytes = "Pelé".as_bytes(); // last char is actually 2 bytes 0xc3_a9
+    /// let s = StringBuilder::new()
+    ///         .append_chunk(&some_bytes[0..4]).unwrap() // ends right after the é's first byte, 0xc3
+    ///         .append_chunk(&some_bytes[4..]).unwrap()  // ... and the second, 0xa9, completes it
+    ///         .to_string();
+    /// assert_eq!(s, "Pelé");
+    /// ```
+    pub fn append_chunk(mut self, from: &[u8]) -> Result<Self, std::str::Utf8Error> {
+        if self.tail_len == 0 {
+            return self.push_chunk_bytes(from);
+        }
+
+        let mut combined = Vec::with_capacity(self.tail_len as usize + from.len());
+        combined.extend_from_slice(&self.tail[..self.tail_len as usize]);
+        combined.extend_from_slice(from);
+        self.tail_len = 0;
+        self.push_chunk_bytes(&combined)
+    }
+
+    /// Push as much of `bytes` as is valid utf8, carrying a trailing incomplete
+    /// sequence into `self.tail` instead of treating it as an error.
+    fn push_chunk_bytes(mut self, bytes: &[u8]) -> Result<Self, std::str::Utf8Error> {
+        match std::str::from_utf8(bytes) {
+            Ok(valid) => {
+                self.buf.buf_push_str(valid);
+                Ok(self)
+            }
+            Err(e) => {
+                let valid_up_to = e.valid_up_to();
+                self.buf
+                    .buf_push_str(std::str::from_utf8(&bytes[..valid_up_to]).unwrap());
+
+                match e.error_len() {
+                    // incomplete sequence at the end of the chunk: stash it and await more input
+                    None => {
+                        let remaining = &bytes[valid_up_to..];
+                        self.tail[..remaining.len()].copy_from_slice(remaining);
+                        self.tail_len = remaining.len() as u8;
+                        Ok(self)
+                    }
+                    // genuinely malformed sequence
+                    Some(_) => Err(e),
+                }
+            }
+        }
+    }
+
+    /// Append an array of bytes, replacing any invalid or incomplete utf8 sequences
+    /// with the Unicode replacement character (`\u{FFFD}`) instead of erroring.
+    ///
+    /// Mirrors [String::from_utf8_lossy], so untrusted byte streams can be appended
+    /// without threading a `Result` or risking a panic.
+    ///
+    /// ```rust
+    /// use string_builder::StringBuilder;
+    ///
+    /// let mut bytes = b"ab".to_vec();
+    /// bytes.push(0xff); // not a valid utf8 lead byte
+    /// bytes.extend_from_slice(b"cd");
+    ///
+    /// let s = StringBuilder::new().append_bytes_lossy(&bytes).to_string();
+    /// assert_eq!(s, "ab\u{FFFD}cd");
+    /// ```
+    pub fn append_bytes_lossy(mut self, from: &[u8]) -> Self {
+        let mut rest = from;
+
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(valid) => {
+                    self.buf.buf_push_str(valid);
+                    break;
+                }
+                Err(e) => {
+                    let valid_up_to = e.valid_up_to();
+                    self.buf
+                        .buf_push_str(std::str::from_utf8(&rest[..valid_up_to]).unwrap());
+                    self.buf.buf_push(char::REPLACEMENT_CHARACTER);
+
+                    let bad_len = e.error_len().unwrap_or(rest.len() - valid_up_to);
+                    rest = &rest[valid_up_to + bad_len..];
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Append a slice of UTF-16 code units, e.g. from Windows APIs or JS/JNI interop.
+    ///
+    /// Mirrors [String::from_utf16]: propagates the underlying
+    /// [std::char::DecodeUtf16Error] if `from` contains an unpaired surrogate.
+    ///
+    /// ```rust
+    /// use string_builder::StringBuilder;
+    ///
+    /// let units: Vec<u16> = "abc".encode_utf16().collect();
+    /// let s = StringBuilder::new().append_utf16(&units).unwrap().to_string();
+    /// assert_eq!(s, "abc");
+    /// ```
+    pub fn append_utf16(mut self, from: &[u16]) -> Result<Self, std::char::DecodeUtf16Error> {
+        for c in char::decode_utf16(from.iter().copied()) {
+            self.buf.buf_push(c?);
+        }
+        Ok(self)
+    }
+
+    /// Append a slice of UTF-16 code units, replacing unpaired surrogates with the
+    /// Unicode replacement character (`\u{FFFD}`) instead of erroring.
+    ///
+    /// Mirrors [String::from_utf16_lossy].
+    ///
+    /// ```rust
+    /// use string_builder::StringBuilder;
+    ///
+    /// let units = [0xD800]; // lone surrogate, invalid on its own
+    /// let s = StringBuilder::new().append_utf16_lossy(&units).to_string();
+    /// assert_eq!(s, "\u{FFFD}");
+    /// ```
+    pub fn append_utf16_lossy(mut self, from: &[u16]) -> Self {
+        for c in char::decode_utf16(from.iter().copied()) {
+            self.buf.buf_push(c.unwrap_or(char::REPLACEMENT_CHARACTER));
+        }
+        self
+    }
+
+    /// Append a [str] by reference, without consuming or returning an owned builder.
+    ///
+    /// Pairs with the consuming [Self::append] for callers holding a `&mut StringBuilder`
+    /// rather than chaining through owned values, e.g. the [std::fmt::Write] impl below.
+    pub fn append_str(&mut self, from: &str) -> &mut Self {
+        self.buf.buf_push_str(from);
+        self
+    }
+}
+
+/// Lets `write!`/`writeln!` target a [StringBuilder] directly, e.g.
+/// `write!(&mut builder, "x={x} y={y}")`, avoiding the intermediate [String]
+/// allocation that `.append(&format!(...))` would otherwise require.
+impl<B: Buffer> std::fmt::Write for StringBuilder<B> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.append_str(s);
+        Ok(())
+    }
+}
+
+/// `builder + "x"`, mirroring [String]'s `Add<&str>`.
+impl<B: Buffer> std::ops::Add<&str> for StringBuilder<B> {
+    type Output = Self;
+
+    fn add(self, rhs: &str) -> Self {
+        self.append(rhs)
+    }
+}
+
+/// `builder += "x"`, mirroring [String]'s `AddAssign<&str>`.
+impl<B: Buffer> std::ops::AddAssign<&str> for StringBuilder<B> {
+    fn add_assign(&mut self, rhs: &str) {
+        self.append_str(rhs);
     }
 }
 
@@ -210,5 +518,187 @@ mod tests {
         };
     }
 
-    
+    #[test]
+    fn build_with_append_chunk() -> Result<(), Utf8Error> {
+        let (sample, sample_bytes) = byte_data();
+
+        // split right in the middle of the 2-byte é, same boundary as append_bytes_panic
+        let s = StringBuilder::new()
+            .append_chunk(&sample_bytes[0..=6])?
+            .append_chunk(&sample_bytes[7..])?
+            .to_string();
+
+        assert_eq!(s, sample, "chunk split mid-codepoint");
+
+        Ok(())
+    }
+
+    #[test]
+    fn append_chunk_splits_every_which_way() -> Result<(), Utf8Error> {
+        let (sample, sample_bytes) = byte_data();
+
+        let mut b = StringBuilder::new();
+        for byte in sample_bytes {
+            b = b.append_chunk(std::slice::from_ref(byte))?;
+        }
+
+        assert_eq!(b.to_string(), sample, "one byte at a time");
+
+        Ok(())
+    }
+
+    #[test]
+    fn append_chunk_malformed_sequence_errors() {
+        let (_sample, sample_bytes) = byte_data();
+
+        // byte 0 (0xe2) starts a 3-byte sequence; replacing byte 1 with an
+        // ASCII byte makes the sequence malformed, not merely incomplete.
+        let mut bad = sample_bytes[0..3].to_vec();
+        bad[1] = b'a';
+
+        let result = StringBuilder::new().append_chunk(&bad);
+
+        assert!(result.is_err(), "malformed sequence should error");
+    }
+
+    #[test]
+    #[should_panic(expected = "stream ended mid-codepoint")]
+    fn to_string_panics_on_unfinished_chunk() {
+        let (_sample, sample_bytes) = byte_data();
+
+        let _s = StringBuilder::new()
+            .append_chunk(&sample_bytes[0..=6])
+            .unwrap()
+            .to_string();
+    }
+
+    #[test]
+    fn append_bytes_lossy_replaces_malformed_sequence() {
+        let mut bytes = b"ab".to_vec();
+        bytes.push(0xff); // not a valid utf8 lead byte
+        bytes.extend_from_slice(b"cd");
+
+        let s = StringBuilder::new().append_bytes_lossy(&bytes).to_string();
+
+        assert_eq!(s, "ab\u{FFFD}cd");
+    }
+
+    #[test]
+    fn append_bytes_lossy_replaces_incomplete_trailing_sequence() {
+        let (_sample, sample_bytes) = byte_data();
+
+        // ends mid-codepoint, same split used by append_bytes_panic
+        let s = StringBuilder::new()
+            .append_bytes_lossy(&sample_bytes[0..=6])
+            .to_string();
+
+        assert_eq!(s, "„Pel\u{FFFD}");
+    }
+
+    #[test]
+    fn append_bytes_lossy_passes_through_valid_utf8() {
+        let (sample, sample_bytes) = byte_data();
+
+        let s = StringBuilder::new().append_bytes_lossy(sample_bytes).to_string();
+
+        assert_eq!(s, sample);
+    }
+
+    #[test]
+    fn build_with_utf16() {
+        let (sample, _sample_bytes) = byte_data();
+
+        let units: Vec<u16> = sample.encode_utf16().collect();
+
+        let s = StringBuilder::new()
+            .append_utf16(&units[0..5])
+            .unwrap()
+            .append_utf16(&units[5..])
+            .unwrap()
+            .to_string();
+
+        assert_eq!(s, sample);
+    }
+
+    #[test]
+    fn append_utf16_unpaired_surrogate_errors() {
+        let units = [0xD800]; // lone high surrogate
+
+        let result = StringBuilder::new().append_utf16(&units);
+
+        assert!(result.is_err(), "unpaired surrogate should error");
+    }
+
+    #[test]
+    fn append_utf16_lossy_replaces_unpaired_surrogate() {
+        let units = [b'a' as u16, 0xD800, b'b' as u16];
+
+        let s = StringBuilder::new().append_utf16_lossy(&units).to_string();
+
+        assert_eq!(s, "a\u{FFFD}b");
+    }
+
+    #[test]
+    fn build_with_append_str() {
+        let mut b = StringBuilder::new();
+        b.append_str("abc").append_str("def");
+
+        assert_eq!(b.to_string(), "abcdef");
+    }
+
+    #[test]
+    fn write_macro_targets_builder() {
+        use std::fmt::Write;
+
+        let mut b = StringBuilder::new();
+        write!(&mut b, "x={} y={}", 1, 2).unwrap();
+
+        assert_eq!(b.to_string(), "x=1 y=2");
+    }
+
+    #[test]
+    fn build_with_append_char() {
+        let s = StringBuilder::new()
+            .append_char('a')
+            .append_char('b')
+            .append_char('c')
+            .to_string();
+
+        assert_eq!(s, "abc");
+    }
+
+    #[test]
+    fn build_with_append_repeated() {
+        let s = StringBuilder::new()
+            .append("indent:")
+            .append_repeated("  ", 3)
+            .append_char_repeated('!', 2)
+            .to_string();
+
+        assert_eq!(s, "indent:      !!");
+    }
+
+    #[test]
+    fn build_with_add_operators() {
+        let s = (StringBuilder::new() + "abc" + "def").to_string();
+        assert_eq!(s, "abcdef");
+
+        let mut b = StringBuilder::new();
+        b += "abc";
+        b += "def";
+        assert_eq!(b.to_string(), "abcdef");
+    }
+
+    #[cfg(feature = "bumpalo")]
+    #[test]
+    fn build_with_bump_arena() {
+        let bump = bumpalo::Bump::new();
+
+        let s = StringBuilder::new_in(&bump)
+            .append("abc")
+            .append("def")
+            .into_bump_str();
+
+        assert_eq!(s, "abcdef");
+    }
 }